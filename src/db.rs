@@ -0,0 +1,171 @@
+use rusqlite::{params, Connection};
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Migrations are applied in order and tracked in the `migrations` table so
+/// new statements appended here run once, incrementally, on top of
+/// whatever version an existing database file is already at.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS config (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS completed (
+        region TEXT NOT NULL,
+        boss TEXT NOT NULL,
+        profile TEXT NOT NULL,
+        completed_at INTEGER NOT NULL,
+        PRIMARY KEY (region, boss, profile)
+    )",
+];
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before unix epoch")
+        .as_secs() as i64
+}
+
+/// Thin typed wrapper around the app's single SQLite connection. Owns
+/// schema migrations and the handful of queries the app needs instead of
+/// spreading raw SQL across the UI code.
+pub struct Database {
+    conn: Connection,
+}
+
+impl Database {
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS migrations (
+                version INTEGER PRIMARY KEY,
+                applied_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        let db = Database { conn };
+        db.run_migrations()?;
+        Ok(db)
+    }
+
+    fn run_migrations(&self) -> rusqlite::Result<()> {
+        let current_version: i64 = self.conn.query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM migrations",
+            [],
+            |row| row.get(0),
+        )?;
+
+        for (index, statement) in MIGRATIONS.iter().enumerate() {
+            let version = (index + 1) as i64;
+            if version <= current_version {
+                continue;
+            }
+
+            self.conn.execute(statement, [])?;
+            self.conn.execute(
+                "INSERT INTO migrations (version, applied_at) VALUES (?1, ?2)",
+                params![version, now_unix()],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_config(&self, key: &str) -> rusqlite::Result<Option<String>> {
+        self.conn
+            .query_row(
+                "SELECT value FROM config WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .map(Some)
+            .or_else(|err| match err {
+                rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                other => Err(other),
+            })
+    }
+
+    pub fn set_config(&self, key: &str, value: &str) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT INTO config (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    pub fn load_completed(&self, profile: &str) -> rusqlite::Result<HashSet<(String, String)>> {
+        let mut statement = self
+            .conn
+            .prepare("SELECT region, boss FROM completed WHERE profile = ?1")?;
+        let rows = statement.query_map(params![profile], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut completed = HashSet::new();
+        for row in rows {
+            completed.insert(row?);
+        }
+        Ok(completed)
+    }
+
+    /// Upserts the row when `completed` is true, or deletes it otherwise,
+    /// rather than rewriting the whole completed set on every toggle.
+    pub fn set_completed(
+        &self,
+        region: &str,
+        boss: &str,
+        profile: &str,
+        completed: bool,
+    ) -> rusqlite::Result<()> {
+        if completed {
+            self.conn.execute(
+                "INSERT INTO completed (region, boss, profile, completed_at) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(region, boss, profile) DO UPDATE SET completed_at = excluded.completed_at",
+                params![region, boss, profile, now_unix()],
+            )?;
+        } else {
+            self.conn.execute(
+                "DELETE FROM completed WHERE region = ?1 AND boss = ?2 AND profile = ?3",
+                params![region, boss, profile],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Deletes every completed row belonging to `profile`, used when a
+    /// profile is removed from the picker.
+    pub fn delete_profile(&self, profile: &str) -> rusqlite::Result<()> {
+        self.conn
+            .execute("DELETE FROM completed WHERE profile = ?1", params![profile])?;
+        Ok(())
+    }
+
+    /// Applies many completion changes as a single transaction instead of
+    /// one write per row, for bulk UI actions like "mark selected
+    /// completed" or "complete all in region".
+    pub fn set_completed_batch(
+        &mut self,
+        profile: &str,
+        entries: &[(String, String, bool)],
+    ) -> rusqlite::Result<()> {
+        let tx = self.conn.transaction()?;
+        for (region, boss, completed) in entries {
+            if *completed {
+                tx.execute(
+                    "INSERT INTO completed (region, boss, profile, completed_at) VALUES (?1, ?2, ?3, ?4)
+                     ON CONFLICT(region, boss, profile) DO UPDATE SET completed_at = excluded.completed_at",
+                    params![region, boss, profile, now_unix()],
+                )?;
+            } else {
+                tx.execute(
+                    "DELETE FROM completed WHERE region = ?1 AND boss = ?2 AND profile = ?3",
+                    params![region, boss, profile],
+                )?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
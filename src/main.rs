@@ -1,13 +1,17 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
+mod db;
+mod watcher;
+
+use db::Database;
 use eframe::egui;
 use serde::{Deserialize, Serialize};
-use std::fs::{File, OpenOptions};
+use std::collections::{BTreeMap, HashSet};
+use std::fs::File;
 use std::io::Read;
-use std::path::Path;
-use std::{collections::HashSet, io::Write};
+use watcher::ChecklistWatcher;
 
-static CONFIG_PATH: &str = "config.json";
+static DB_PATH: &str = "boss_checker.db";
 
 fn main() -> Result<(), eframe::Error> {
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
@@ -24,24 +28,116 @@ fn main() -> Result<(), eframe::Error> {
     )
 }
 
-fn filter_entries(entries: &mut Vec<TableEntry>, search_region: &String, search_boss: &String) {
-    let term = search_boss.to_lowercase();
-    for entry in entries.iter_mut() {
-        // TODO: replace with fuzzy search
-        if search_region == "All" || entry.region == search_region.as_str() {
-            entry.visible = true;
-        }
-        else {
-            entry.visible = false;
+/// Scores how well `query` matches `target` as an ordered, non-contiguous
+/// subsequence (fuzzy match). Returns `None` when `query` is not a
+/// subsequence of `target` at all, in which case the entry should be
+/// filtered out. A higher score means a better match.
+///
+/// Scoring rules:
+/// - every matched char is worth a base point
+/// - consecutive matched chars earn a contiguity bonus
+/// - a match that lands at a word boundary (start of string, or right
+///   after a space/punctuation) earns a larger bonus
+/// - target chars skipped before the first match incur a small penalty
+fn fuzzy_score(query: &str, target: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    const MATCH_SCORE: i32 = 10;
+    const CONTIGUOUS_BONUS: i32 = 8;
+    const WORD_BOUNDARY_BONUS: i32 = 15;
+    const SKIP_PENALTY: i32 = 1;
+
+    let query_lower = query.to_lowercase();
+    let target_lower = target.to_lowercase();
+    let target_chars: Vec<char> = target_lower.chars().collect();
+
+    let mut score = 0;
+    let mut target_idx = 0;
+    let mut prev_matched_idx: Option<usize> = None;
+    let mut first_match_idx: Option<usize> = None;
+
+    for query_char in query_lower.chars() {
+        let mut found = false;
+        while target_idx < target_chars.len() {
+            let target_char = target_chars[target_idx];
+            if target_char == query_char {
+                score += MATCH_SCORE;
+
+                if let Some(prev_idx) = prev_matched_idx {
+                    if target_idx == prev_idx + 1 {
+                        score += CONTIGUOUS_BONUS;
+                    }
+                }
+
+                let at_word_boundary = target_idx == 0
+                    || target_chars
+                        .get(target_idx - 1)
+                        .map(|c| *c == ' ' || c.is_ascii_punctuation())
+                        .unwrap_or(false);
+                if at_word_boundary {
+                    score += WORD_BOUNDARY_BONUS;
+                }
+
+                if first_match_idx.is_none() {
+                    first_match_idx = Some(target_idx);
+                }
+
+                prev_matched_idx = Some(target_idx);
+                target_idx += 1;
+                found = true;
+                break;
+            }
+            target_idx += 1;
         }
 
-        if entry.name.to_lowercase().contains(&term) || entry.region.to_lowercase().contains(&term)
-        {
-            entry.visible &= true;
-        } else {
-            entry.visible &= false;
+        if !found {
+            return None;
         }
     }
+
+    if let Some(first_idx) = first_match_idx {
+        score -= first_idx as i32 * SKIP_PENALTY;
+    }
+
+    Some(score)
+}
+
+fn filter_entries(entries: &mut Vec<TableEntry>, search_region: &String, search_boss: &String) {
+    for entry in entries.iter_mut() {
+        let region_matches = search_region == "All" || entry.region == search_region.as_str();
+
+        let region_score = fuzzy_score(search_boss, &entry.region);
+        let name_score = fuzzy_score(search_boss, &entry.name);
+        let best_score = match (region_score, name_score) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (Some(a), None) => Some(a),
+            (None, Some(b)) => Some(b),
+            (None, None) => None,
+        };
+
+        entry.visible = region_matches && best_score.is_some();
+        // Only meaningful for visible rows; never let a filtered-out entry
+        // carry a stale score that could outrank a visible one.
+        entry.score = if entry.visible { best_score.unwrap_or(0) } else { 0 };
+    }
+}
+
+/// Indices of the visible entries, ordered by descending fuzzy score for
+/// rendering. `entries` itself is left in stable (load) order so raw
+/// indices into it stay valid across frames; only this view is re-sorted
+/// as the search term changes.
+fn visible_view_order(entries: &[TableEntry]) -> Vec<usize> {
+    let mut order: Vec<usize> = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| entry.visible)
+        .map(|(index, _)| index)
+        .collect();
+
+    order.sort_by(|&a, &b| entries[b].score.cmp(&entries[a].score));
+    order
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -64,6 +160,8 @@ fn load_tables_from_file(file_path: String, state: &State) -> Vec<TableEntry> {
                 region: data.region.clone(),
                 name: boss.clone(),
                 visible: true,
+                score: 0,
+                selected: false,
                 checked: state
                     .completed
                     .contains(&(data.region.clone(), boss.clone())),
@@ -89,96 +187,113 @@ fn extract_regions(entries: &Vec<TableEntry>) -> Vec<String> {
     vec
 }
 
+/// Rolls entries up into an overall (completed, total) count plus a
+/// per-region breakdown, keyed and ordered by region name.
+fn completion_stats(
+    entries: &Vec<TableEntry>,
+) -> ((usize, usize), BTreeMap<String, (usize, usize)>) {
+    let mut by_region: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    let mut overall = (0usize, 0usize);
+
+    for entry in entries.iter() {
+        let counts = by_region.entry(entry.region.clone()).or_insert((0, 0));
+        counts.1 += 1;
+        overall.1 += 1;
+        if entry.checked {
+            counts.0 += 1;
+            overall.0 += 1;
+        }
+    }
+
+    (overall, by_region)
+}
+
 struct TableEntry {
     region: String,
     name: String,
     checked: bool,
     visible: bool,
+    score: i32,
+    selected: bool,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+static DEFAULT_PROFILE_NAME: &str = "default";
+
+#[derive(Debug, Clone)]
 struct Config {
     checklist_path: String,
-    default_save: String,
+    profiles: Vec<String>,
+    active_profile: String,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
             checklist_path: "boss_data.json".to_string(),
-            default_save: "default_save.json".to_string(),
+            profiles: vec![DEFAULT_PROFILE_NAME.to_string()],
+            active_profile: DEFAULT_PROFILE_NAME.to_string(),
         }
     }
 }
 
 impl Config {
-    fn make_or_load_from_file() -> Self {
-        let file_path = Path::new(CONFIG_PATH);
-        let exists = file_path.exists();
-        let mut file: std::fs::File;
-
-        if !exists {
-            file = File::create(CONFIG_PATH).unwrap();
-
-            let created = Config::default();
-            let buf = serde_json::to_string(&created).unwrap();
-            match file.write_all(buf.as_bytes()) {
-                Ok(_) => {}
-                Err(e) => {
-                    eprint!("{}", e)
-                }
+    fn make_or_load_from_file(db: &Database) -> Self {
+        let default_config = Config::default();
+
+        let checklist_path = match db.get_config("checklist_path").unwrap() {
+            Some(value) => value,
+            None => {
+                db.set_config("checklist_path", &default_config.checklist_path)
+                    .unwrap();
+                default_config.checklist_path
             }
-        }
+        };
+
+        let profiles = match db.get_config("profiles").unwrap() {
+            Some(value) => serde_json::from_str(&value).unwrap(),
+            None => {
+                let serialized = serde_json::to_string(&default_config.profiles).unwrap();
+                db.set_config("profiles", &serialized).unwrap();
+                default_config.profiles
+            }
+        };
+
+        let active_profile = match db.get_config("active_profile").unwrap() {
+            Some(value) => value,
+            None => {
+                db.set_config("active_profile", &default_config.active_profile)
+                    .unwrap();
+                default_config.active_profile
+            }
+        };
 
-        file = OpenOptions::new().read(true).open(CONFIG_PATH).unwrap();
-        let mut data = String::new();
-        file.read_to_string(&mut data).unwrap();
-        let config: Config = serde_json::from_str(&data).unwrap();
+        Config {
+            checklist_path,
+            profiles,
+            active_profile,
+        }
+    }
 
-        config
+    /// Writes the profile list and active profile back to the db; called
+    /// whenever either changes so a restart picks up where the user left
+    /// off.
+    fn persist_profiles(&self, db: &Database) {
+        let serialized = serde_json::to_string(&self.profiles).unwrap();
+        db.set_config("profiles", &serialized).unwrap();
+        db.set_config("active_profile", &self.active_profile)
+            .unwrap();
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 struct State {
     completed: HashSet<(String, String)>,
 }
 
 impl State {
-    fn make_or_load_from_file() -> Self {
-        let config = Config::make_or_load_from_file();
-        let save_file_name = config.default_save;
-
-        let file_path = Path::new(&save_file_name);
-        let exists = file_path.exists();
-        let mut file: std::fs::File;
-
-        if !exists {
-            file = File::create(&save_file_name).unwrap();
-
-            let created = State::default();
-            let buf = serde_json::to_string(&created).unwrap();
-            match file.write_all(buf.as_bytes()) {
-                Ok(_) => {}
-                Err(e) => {
-                    eprint!("{}", e)
-                }
-            }
-        }
-
-        file = OpenOptions::new().read(true).open(save_file_name).unwrap();
-        let mut data = String::new();
-        file.read_to_string(&mut data).unwrap();
-        let state: State = serde_json::from_str(&data).unwrap();
-
-        state
-    }
-}
-
-impl Default for State {
-    fn default() -> Self {
+    fn make_or_load_from_file(db: &Database, profile: &str) -> Self {
         State {
-            completed: HashSet::new(),
+            completed: db.load_completed(profile).unwrap(),
         }
     }
 }
@@ -188,33 +303,48 @@ struct MyApp {
     boss_filter: String,
     config: Config,
     entries: Vec<TableEntry>,
-    filter_regions: Vec<String>
+    filter_regions: Vec<String>,
+    db: Database,
+    last_clicked: Option<(String, String)>,
+    checklist_watcher: ChecklistWatcher,
+    new_profile_name: String,
 }
 
 impl MyApp {
-    fn save_to_disk(&mut self) {
-        let mut hash_set: HashSet<(String,String)> = HashSet::new();
-        for entry in self.entries.iter() {
-            if entry.checked {
-                hash_set.insert((entry.region.clone(), entry.name.clone()));
-            }
+    /// Persists every changed row as a single transaction rather than one
+    /// write per row, whether the change came from one checkbox or a bulk
+    /// toolbar action.
+    fn save_entries(&mut self, entries: &[(String, String, bool)]) {
+        if entries.is_empty() {
+            return;
         }
 
-        let path = Path::new(&self.config.default_save);
-        let mut file = File::options().write(true).truncate(true).open(path).unwrap();
-        let save_state = State{completed: hash_set};
-        let serialized = serde_json::to_string(&save_state).unwrap();
+        self.db
+            .set_completed_batch(&self.config.active_profile, entries)
+            .expect("Failed to persist completion state");
+    }
 
-        file.write_all(serialized.as_bytes()).expect("Failed to write save file");
+    /// Reloads every entry's `checked` flag from the active profile's
+    /// stored completions without touching the boss list itself.
+    fn reload_checked_from_active_profile(&mut self) {
+        let state = State::make_or_load_from_file(&self.db, &self.config.active_profile);
+        for entry in self.entries.iter_mut() {
+            entry.checked = state
+                .completed
+                .contains(&(entry.region.clone(), entry.name.clone()));
+        }
     }
 }
 
 impl Default for MyApp {
     fn default() -> Self {
-        let loaded_config = Config::make_or_load_from_file();
-        let loaded_state = State::make_or_load_from_file();
+        let db = Database::open(DB_PATH).expect("Failed to open boss_checker.db");
+        let loaded_config = Config::make_or_load_from_file(&db);
+        let loaded_state = State::make_or_load_from_file(&db, &loaded_config.active_profile);
         let loaded_data =
             load_tables_from_file(loaded_config.checklist_path.clone(), &loaded_state);
+        let checklist_watcher = ChecklistWatcher::new(&loaded_config.checklist_path)
+            .expect("Failed to watch checklist file");
 
         Self {
             boss_filter: "".to_owned(),
@@ -222,17 +352,109 @@ impl Default for MyApp {
             config: loaded_config,
             filter_regions: extract_regions(&loaded_data),
             entries: loaded_data,
+            db,
+            last_clicked: None,
+            checklist_watcher,
+            new_profile_name: "".to_owned(),
         }
     }
 }
 
 impl eframe::App for MyApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        filter_entries(&mut self.entries, &self.region_filter,&self.boss_filter);
-        let mut dirty: bool = false;
+        if self.checklist_watcher.poll_changed() {
+            let current_state =
+                State::make_or_load_from_file(&self.db, &self.config.active_profile);
+            let reloaded_data =
+                load_tables_from_file(self.config.checklist_path.clone(), &current_state);
+            self.filter_regions = extract_regions(&reloaded_data);
+            self.entries = reloaded_data;
+            // The old last-clicked row may no longer exist in the reloaded
+            // list, so any pending shift-click anchor is stale.
+            self.last_clicked = None;
+            ctx.request_repaint();
+        }
+
+        filter_entries(&mut self.entries, &self.region_filter, &self.boss_filter);
+        let view = visible_view_order(&self.entries);
+        let view_positions: std::collections::HashMap<(String, String), usize> = view
+            .iter()
+            .enumerate()
+            .map(|(pos, &index)| {
+                let entry = &self.entries[index];
+                ((entry.region.clone(), entry.name.clone()), pos)
+            })
+            .collect();
+        let mut changed_entries: Vec<(String, String, bool)> = Vec::new();
+        let mut select_range: Vec<(usize, usize)> = Vec::new();
+        let shift_held = ctx.input(|i| i.modifiers.shift);
+
+        let mut mark_selected_completed = false;
+        let mut mark_selected_incomplete = false;
+        let mut invert_selection = false;
+        let mut complete_all_in_region = false;
+        let mut clear_all_in_region = false;
+        let mut profile_switched = false;
+        let mut new_profile_clicked = false;
+        let mut duplicate_profile_clicked = false;
+        let mut delete_profile_clicked = false;
+
+        let (overall, region_stats) = completion_stats(&self.entries);
 
         egui::CentralPanel::default().show(ctx, |ui| {
             //ui.heading("Boss Picker");
+            let (completed, total) = overall;
+            ui.add(
+                egui::ProgressBar::new(completed as f32 / total.max(1) as f32)
+                    .text(format!("{} / {} bosses", completed, total)),
+            );
+
+            egui::CollapsingHeader::new("Per-region progress").show(ui, |ui| {
+                for (region, (region_completed, region_total)) in region_stats.iter() {
+                    ui.horizontal(|ui| {
+                        ui.label(region);
+                        ui.add(
+                            egui::ProgressBar::new(
+                                *region_completed as f32 / (*region_total).max(1) as f32,
+                            )
+                            .text(format!("{} / {}", region_completed, region_total)),
+                        );
+                    });
+                }
+            });
+
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                egui::ComboBox::new("ProfileCombo", "Profile")
+                    .selected_text(self.config.active_profile.to_string())
+                    .show_ui(ui, |ui| {
+                        for profile in self.config.profiles.clone() {
+                            if ui
+                                .selectable_value(
+                                    &mut self.config.active_profile,
+                                    profile.clone(),
+                                    profile.clone(),
+                                )
+                                .clicked()
+                            {
+                                profile_switched = true;
+                            }
+                        }
+                    });
+
+                ui.text_edit_singleline(&mut self.new_profile_name);
+                if ui.button("New profile").clicked() {
+                    new_profile_clicked = true;
+                }
+                if ui.button("Duplicate").clicked() {
+                    duplicate_profile_clicked = true;
+                }
+                if ui.button("Delete").clicked() {
+                    delete_profile_clicked = true;
+                }
+            });
+
             ui.horizontal(|ui| {
                 egui::ComboBox::new("Combo", "").width(200.0).selected_text(self.region_filter.to_string()).show_ui(ui, |ui|{
                     for region in self.filter_regions.iter_mut() {
@@ -244,31 +466,148 @@ impl eframe::App for MyApp {
                 ui.text_edit_singleline(&mut self.boss_filter)
                     .labelled_by(name_label.id);
             });
-            
+
+            ui.horizontal(|ui| {
+                if ui.button("Mark selected completed").clicked() {
+                    mark_selected_completed = true;
+                }
+                if ui.button("Mark selected incomplete").clicked() {
+                    mark_selected_incomplete = true;
+                }
+                if ui.button("Invert selection").clicked() {
+                    invert_selection = true;
+                }
+
+                if self.region_filter != "All" {
+                    if ui.button("Complete all in region").clicked() {
+                        complete_all_in_region = true;
+                    }
+                    if ui.button("Clear all in region").clicked() {
+                        clear_all_in_region = true;
+                    }
+                }
+            });
 
             egui::ScrollArea::vertical().show(ui, |ui| {
                 egui::Grid::new("Grid").min_col_width(150.0).striped(true).show(ui, |ui| {
-                    for entry in self.entries.iter_mut() {
-                        if !entry.visible {
-                            continue;
+                    for (pos, &index) in view.iter().enumerate() {
+                        let entry = &mut self.entries[index];
+
+                        if ui.checkbox(&mut entry.selected, "").clicked() {
+                            let identity = (entry.region.clone(), entry.name.clone());
+                            if shift_held {
+                                if let Some(last_identity) = &self.last_clicked {
+                                    if let Some(&last_pos) = view_positions.get(last_identity) {
+                                        select_range.push((last_pos.min(pos), last_pos.max(pos)));
+                                    }
+                                }
+                            }
+                            self.last_clicked = Some(identity);
                         }
-    
+
                         ui.label(&entry.region);
                         ui.label(&entry.name);
-    
+
                         if ui.checkbox(&mut entry.checked, "Completed").changed() {
-                            dirty = true;
+                            changed_entries.push((
+                                entry.region.clone(),
+                                entry.name.clone(),
+                                entry.checked,
+                            ));
                         }
-    
+
                         ui.end_row();
                     }
                 });
             });
-            
+
         });
 
-        if dirty {
-            self.save_to_disk();
+        if let Some((start, end)) = select_range.first() {
+            for &index in &view[*start..=*end] {
+                self.entries[index].selected = true;
+            }
+        }
+
+        if mark_selected_completed || mark_selected_incomplete {
+            for entry in self.entries.iter_mut() {
+                if entry.visible && entry.selected {
+                    let checked = mark_selected_completed;
+                    if entry.checked != checked {
+                        entry.checked = checked;
+                        changed_entries.push((entry.region.clone(), entry.name.clone(), checked));
+                    }
+                }
+            }
+        }
+
+        if invert_selection {
+            for entry in self.entries.iter_mut() {
+                if entry.visible {
+                    entry.selected = !entry.selected;
+                }
+            }
+        }
+
+        if complete_all_in_region || clear_all_in_region {
+            let checked = complete_all_in_region;
+            for entry in self.entries.iter_mut() {
+                if entry.region == self.region_filter && entry.checked != checked {
+                    entry.checked = checked;
+                    changed_entries.push((entry.region.clone(), entry.name.clone(), checked));
+                }
+            }
+        }
+
+        if new_profile_clicked {
+            let name = self.new_profile_name.trim().to_string();
+            if !name.is_empty() && !self.config.profiles.contains(&name) {
+                self.config.profiles.push(name.clone());
+                self.config.active_profile = name;
+                self.config.persist_profiles(&self.db);
+                self.new_profile_name.clear();
+                self.reload_checked_from_active_profile();
+            }
+        }
+
+        if duplicate_profile_clicked {
+            let name = self.new_profile_name.trim().to_string();
+            if !name.is_empty() && !self.config.profiles.contains(&name) {
+                let copied: Vec<(String, String, bool)> = self
+                    .entries
+                    .iter()
+                    .filter(|entry| entry.checked)
+                    .map(|entry| (entry.region.clone(), entry.name.clone(), true))
+                    .collect();
+                self.db
+                    .set_completed_batch(&name, &copied)
+                    .expect("Failed to duplicate profile");
+
+                self.config.profiles.push(name.clone());
+                self.config.active_profile = name;
+                self.config.persist_profiles(&self.db);
+                self.new_profile_name.clear();
+                self.reload_checked_from_active_profile();
+            }
         }
+
+        if delete_profile_clicked && self.config.profiles.len() > 1 {
+            let removed = self.config.active_profile.clone();
+            self.db
+                .delete_profile(&removed)
+                .expect("Failed to delete profile");
+
+            self.config.profiles.retain(|profile| profile != &removed);
+            self.config.active_profile = self.config.profiles[0].clone();
+            self.config.persist_profiles(&self.db);
+            self.reload_checked_from_active_profile();
+        }
+
+        if profile_switched {
+            self.config.persist_profiles(&self.db);
+            self.reload_checked_from_active_profile();
+        }
+
+        self.save_entries(&changed_entries);
     }
 }
@@ -0,0 +1,75 @@
+use notify::{recommended_watcher, Event, RecommendedWatcher, RecursiveMode, Watcher as _};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Watches the boss checklist file and reports when it has settled after a
+/// change, so a single editor save doesn't trigger several reloads in a
+/// row.
+///
+/// Only the checklist file is watched. `Config` is stored in the SQLite db
+/// rather than in a config file, so there is no config file to watch. The
+/// db itself isn't watched either: this process is the only writer, so a
+/// filesystem event on it would just be our own save.
+pub struct ChecklistWatcher {
+    _watcher: RecommendedWatcher,
+    receiver: Receiver<notify::Result<Event>>,
+    checklist_file_name: OsString,
+    pending_since: Option<Instant>,
+}
+
+impl ChecklistWatcher {
+    pub fn new(checklist_path: &str) -> notify::Result<Self> {
+        let path = Path::new(checklist_path);
+        // Watch the parent directory rather than the file itself: most
+        // editors save by writing a temp file and renaming it over the
+        // original, which replaces the inode and would silently end a
+        // watch on the file path directly.
+        let watch_dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => PathBuf::from(parent),
+            _ => PathBuf::from("."),
+        };
+        let checklist_file_name = path
+            .file_name()
+            .expect("checklist_path must name a file")
+            .to_os_string();
+
+        let (tx, rx) = channel();
+        let mut watcher = recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        })?;
+        watcher.watch(&watch_dir, RecursiveMode::NonRecursive)?;
+
+        Ok(Self {
+            _watcher: watcher,
+            receiver: rx,
+            checklist_file_name,
+            pending_since: None,
+        })
+    }
+
+    /// Drains any pending filesystem events and returns `true` once, after
+    /// the watched file has been quiet for `DEBOUNCE`.
+    pub fn poll_changed(&mut self) -> bool {
+        while let Ok(Ok(event)) = self.receiver.try_recv() {
+            let matches_checklist = event
+                .paths
+                .iter()
+                .any(|path| path.file_name() == Some(self.checklist_file_name.as_os_str()));
+            if matches_checklist {
+                self.pending_since = Some(Instant::now());
+            }
+        }
+
+        match self.pending_since {
+            Some(seen_at) if seen_at.elapsed() >= DEBOUNCE => {
+                self.pending_since = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}